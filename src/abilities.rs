@@ -0,0 +1,31 @@
+/// Describes what a camera driver supports, as reported by libgphoto2's
+/// `gp_camera_get_abilities`.
+pub struct Abilities {
+    inner: ::gphoto2::CameraAbilities,
+}
+
+pub fn from_libgphoto2(abilities: ::gphoto2::CameraAbilities) -> Abilities {
+    Abilities { inner: abilities }
+}
+
+impl Abilities {
+    /// Whether the driver supports deleting files via `Camera::delete_file`.
+    pub fn can_delete_file(&self) -> bool {
+        self.inner.file_operations & ::gphoto2::GP_FILE_OPERATION_DELETE != 0
+    }
+
+    /// Whether the driver supports uploading files via `Camera::upload_file`.
+    pub fn can_upload_file(&self) -> bool {
+        self.inner.folder_operations & ::gphoto2::GP_FOLDER_OPERATION_PUT_FILE != 0
+    }
+
+    /// Whether the driver supports creating folders via `Camera::make_dir`.
+    pub fn can_make_dir(&self) -> bool {
+        self.inner.folder_operations & ::gphoto2::GP_FOLDER_OPERATION_MAKE_DIR != 0
+    }
+
+    /// Whether the driver supports removing folders via `Camera::remove_dir`.
+    pub fn can_remove_dir(&self) -> bool {
+        self.inner.folder_operations & ::gphoto2::GP_FOLDER_OPERATION_REMOVE_DIR != 0
+    }
+}
@@ -1,15 +1,18 @@
 use std::borrow::Cow;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::mem;
 use std::ptr;
 use std::fmt;
+use std::slice;
 use libc::{c_int, free};
 
 use ::context::Context;
 use ::abilities::Abilities;
-use ::media::Media;
+use ::media::{Media, MemoryFile};
 use ::port::Port;
 use ::storage::Storage;
+use ::widget::CameraWidget;
+use ::list::CameraListIter;
 
 use ::handle::prelude::*;
 
@@ -42,11 +45,16 @@ impl Camera {
 
     /// Captures an image.
     pub fn capture_image(&mut self, context: &mut Context) -> ::Result<CameraFile> {
+        self.capture(context, CaptureType::Image)
+    }
+
+    /// Captures an image, movie, or sound clip, depending on `capture_type`.
+    pub fn capture(&mut self, context: &mut Context, capture_type: CaptureType) -> ::Result<CameraFile> {
         let mut file_path = unsafe { mem::uninitialized() };
 
         try_unsafe! {
             ::gphoto2::gp_camera_capture(self.camera,
-                                         ::gphoto2::GP_CAPTURE_IMAGE,
+                                         capture_type.to_libgphoto2(),
                                          &mut file_path,
                                          context.as_mut_ptr())
         };
@@ -89,13 +97,37 @@ impl Camera {
         }
     }
 
+    /// Captures a single live-view frame for focusing and framing.
+    ///
+    /// Unlike `capture`/`capture_image`, the frame is returned directly as
+    /// an in-memory JPEG and never touches the camera's storage, so it
+    /// doesn't go through `wait_for_file`. Call this in a loop (10-30 fps)
+    /// to drive a live preview.
+    pub fn capture_preview(&mut self, context: &mut Context) -> ::Result<MemoryFile> {
+        let mut file = MemoryFile::new()?;
+
+        try_unsafe! {
+            ::gphoto2::gp_camera_capture_preview(self.camera, file.as_mut_ptr(), context.as_mut_ptr())
+        };
+
+        Ok(file)
+    }
+
     /// Downloads a file from the camera.
     pub fn download<T: Media>(&mut self, context: &mut Context, source: &CameraFile, destination: &mut T) -> ::Result<()> {
+        self.download_with_type(context, source, FileType::Normal, destination)
+    }
+
+    /// Downloads a file from the camera, selecting which variant to fetch.
+    ///
+    /// This is how to fetch a `Preview` thumbnail or just the `Exif` block
+    /// without pulling the full-resolution `Normal` image.
+    pub fn download_with_type<T: Media>(&mut self, context: &mut Context, source: &CameraFile, file_type: FileType, destination: &mut T) -> ::Result<()> {
         try_unsafe! {
             ::gphoto2::gp_camera_file_get(self.camera,
                                           source.inner.folder.as_ptr(),
                                           source.inner.name.as_ptr(),
-                                          ::gphoto2::GP_FILE_TYPE_NORMAL,
+                                          file_type.to_libgphoto2(),
                                           destination.as_mut_ptr(),
                                           context.as_mut_ptr())
         };
@@ -103,6 +135,103 @@ impl Camera {
         Ok(())
     }
 
+    /// Lists the subfolders of `folder` on the camera's storage.
+    pub fn list_folders(&mut self, context: &mut Context, folder: &str) -> ::Result<CameraListIter> {
+        let folder = CString::new(folder).unwrap();
+        let mut ptr = unsafe { mem::uninitialized() };
+
+        try_unsafe!(::gphoto2::gp_list_new(&mut ptr));
+
+        try_unsafe! {
+            ::gphoto2::gp_camera_folder_list_folders(self.camera, folder.as_ptr(), ptr, context.as_mut_ptr())
+        };
+
+        ::list::from_libgphoto2(ptr)
+    }
+
+    /// Lists the files in `folder` on the camera's storage, as full
+    /// `CameraFile` handles ready to pass to `download`/`delete_file`.
+    pub fn list_files(&mut self, context: &mut Context, folder: &str) -> ::Result<CameraFileIter> {
+        let folder_cstr = CString::new(folder).unwrap();
+        let mut ptr = unsafe { mem::uninitialized() };
+
+        try_unsafe!(::gphoto2::gp_list_new(&mut ptr));
+
+        try_unsafe! {
+            ::gphoto2::gp_camera_folder_list_files(self.camera, folder_cstr.as_ptr(), ptr, context.as_mut_ptr())
+        };
+
+        let names = ::list::from_libgphoto2(ptr)?;
+
+        Ok(CameraFileIter { folder: folder.to_owned(), names: names })
+    }
+
+    /// Returns size, modification time, and MIME type for a file on the camera.
+    pub fn file_info(&mut self, context: &mut Context, folder: &str, name: &str) -> ::Result<FileInfo> {
+        let folder = CString::new(folder).unwrap();
+        let name = CString::new(name).unwrap();
+        let mut info = unsafe { mem::uninitialized() };
+
+        try_unsafe! {
+            ::gphoto2::gp_camera_file_get_info(self.camera, folder.as_ptr(), name.as_ptr(), &mut info, context.as_mut_ptr())
+        };
+
+        Ok(FileInfo { inner: info })
+    }
+
+    /// Deletes a file from the camera's storage.
+    ///
+    /// Check `Abilities::can_delete_file` first; not every driver supports this.
+    pub fn delete_file(&mut self, context: &mut Context, file: &CameraFile) -> ::Result<()> {
+        try_unsafe! {
+            ::gphoto2::gp_camera_file_delete(self.camera, file.inner.folder.as_ptr(), file.inner.name.as_ptr(), context.as_mut_ptr())
+        };
+
+        Ok(())
+    }
+
+    /// Uploads a file to a folder on the camera's storage.
+    ///
+    /// Check `Abilities::can_upload_file` first; not every driver supports this.
+    pub fn upload_file<T: Media>(&mut self, context: &mut Context, folder: &str, name: &str, media: &mut T) -> ::Result<()> {
+        let folder = CString::new(folder).unwrap();
+        let name = CString::new(name).unwrap();
+
+        try_unsafe! {
+            ::gphoto2::gp_camera_folder_put_file(self.camera, folder.as_ptr(), name.as_ptr(), media.as_mut_ptr(), context.as_mut_ptr())
+        };
+
+        Ok(())
+    }
+
+    /// Creates a new folder on the camera's storage.
+    ///
+    /// Check `Abilities::can_make_dir` first; not every driver supports this.
+    pub fn make_dir(&mut self, context: &mut Context, folder: &str, name: &str) -> ::Result<()> {
+        let folder = CString::new(folder).unwrap();
+        let name = CString::new(name).unwrap();
+
+        try_unsafe! {
+            ::gphoto2::gp_camera_folder_make_dir(self.camera, folder.as_ptr(), name.as_ptr(), context.as_mut_ptr())
+        };
+
+        Ok(())
+    }
+
+    /// Removes a folder from the camera's storage.
+    ///
+    /// Check `Abilities::can_remove_dir` first; not every driver supports this.
+    pub fn remove_dir(&mut self, context: &mut Context, folder: &str, name: &str) -> ::Result<()> {
+        let folder = CString::new(folder).unwrap();
+        let name = CString::new(name).unwrap();
+
+        try_unsafe! {
+            ::gphoto2::gp_camera_folder_remove_dir(self.camera, folder.as_ptr(), name.as_ptr(), context.as_mut_ptr())
+        };
+
+        Ok(())
+    }
+
     /// Returns information about the port the camera is connected to.
     pub fn port<'a>(&'a self) -> Port<'a> {
         let mut ptr = unsafe { mem::uninitialized() };
@@ -139,10 +268,41 @@ impl Camera {
                                                  context.as_mut_ptr())
         };
 
-        let storage = ptr as *mut Storage;
+        let raw = ptr as *const ::gphoto2::CameraStorageInformation;
         let length = len as usize;
 
-        Ok(unsafe { Vec::from_raw_parts(storage, length, length) })
+        let storage = unsafe { slice::from_raw_parts(raw, length) }
+            .iter()
+            .cloned()
+            .map(::storage::from_libgphoto2)
+            .collect();
+
+        unsafe { free(ptr as *mut _) };
+
+        Ok(storage)
+    }
+
+    /// Returns the root of the camera's configuration tree.
+    ///
+    /// The tree can be walked with `CameraWidget::get`/`child` to find
+    /// individual settings such as ISO, shutter speed, or image format.
+    /// Changes made to widgets in the returned tree only take effect once
+    /// passed back to `set_config`.
+    pub fn config(&mut self, context: &mut Context) -> ::Result<CameraWidget<'static>> {
+        let mut ptr = unsafe { mem::uninitialized() };
+
+        try_unsafe!(::gphoto2::gp_camera_get_config(self.camera, &mut ptr, context.as_mut_ptr()));
+
+        Ok(::widget::from_libgphoto2_root(ptr))
+    }
+
+    /// Writes a (possibly modified) configuration tree back to the camera.
+    pub fn set_config<'a>(&mut self, context: &mut Context, config: &mut CameraWidget<'a>) -> ::Result<()> {
+        try_unsafe! {
+            ::gphoto2::gp_camera_set_config(self.camera, config.as_mut_ptr(), context.as_mut_ptr())
+        };
+
+        Ok(())
     }
 
     /// Returns the camera's summary.
@@ -202,6 +362,54 @@ impl Camera {
 }
 
 
+/// Which kind of capture to perform with `Camera::capture`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureType {
+    Image,
+    Movie,
+    Sound,
+}
+
+impl CaptureType {
+    fn to_libgphoto2(self) -> ::gphoto2::CameraCaptureType {
+        match self {
+            CaptureType::Image => ::gphoto2::GP_CAPTURE_IMAGE,
+            CaptureType::Movie => ::gphoto2::GP_CAPTURE_MOVIE,
+            CaptureType::Sound => ::gphoto2::GP_CAPTURE_SOUND,
+        }
+    }
+}
+
+/// Which variant of a file to fetch with `Camera::download_with_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    /// The full-resolution file, as captured.
+    Normal,
+    /// A small preview/thumbnail image, much faster to fetch than `Normal`.
+    Preview,
+    /// The untouched raw sensor data, for cameras that keep it separate from `Normal`.
+    Raw,
+    /// An audio annotation attached to the file, if any.
+    Audio,
+    /// Just the EXIF block.
+    Exif,
+    /// Driver-specific metadata that isn't part of `Exif`.
+    Metadata,
+}
+
+impl FileType {
+    fn to_libgphoto2(self) -> ::gphoto2::CameraFileType {
+        match self {
+            FileType::Normal => ::gphoto2::GP_FILE_TYPE_NORMAL,
+            FileType::Preview => ::gphoto2::GP_FILE_TYPE_PREVIEW,
+            FileType::Raw => ::gphoto2::GP_FILE_TYPE_RAW,
+            FileType::Audio => ::gphoto2::GP_FILE_TYPE_AUDIO,
+            FileType::Exif => ::gphoto2::GP_FILE_TYPE_EXIF,
+            FileType::Metadata => ::gphoto2::GP_FILE_TYPE_METADATA,
+        }
+    }
+}
+
 /// A file stored on a camera's storage.
 #[derive(Clone)]
 pub struct CameraFile {
@@ -209,6 +417,19 @@ pub struct CameraFile {
 }
 
 impl CameraFile {
+    /// Builds a handle for a file at `folder`/`name` that's already known to
+    /// exist on the camera, e.g. one discovered via `Camera::list_files`.
+    /// This is what lets a listed entry round-trip into `download` or
+    /// `delete_file` without having come from a capture.
+    pub fn new(folder: &str, name: &str) -> ::Result<CameraFile> {
+        let mut inner: ::gphoto2::CameraFilePath = unsafe { mem::zeroed() };
+
+        util::write_c_string(&mut inner.folder, folder)?;
+        util::write_c_string(&mut inner.name, name)?;
+
+        Ok(CameraFile { inner: inner })
+    }
+
     /// Returns the directory that the file is stored in.
     pub fn directory(&self) -> Cow<str> {
         unsafe {
@@ -230,8 +451,65 @@ impl fmt::Debug for CameraFile {
     }
 }
 
+/// An iterator over the files in a folder, yielding full `CameraFile`
+/// handles rather than bare names.
+pub struct CameraFileIter {
+    folder: String,
+    names: CameraListIter,
+}
+
+impl Iterator for CameraFileIter {
+    type Item = ::Result<CameraFile>;
+
+    fn next(&mut self) -> Option<::Result<CameraFile>> {
+        self.names.next().map(|name| CameraFile::new(&self.folder, &name))
+    }
+}
+
+/// Size, timestamp, and MIME type of a file on a camera's storage.
+///
+/// Only the `GP_FILE_INFO_*` fields libgphoto2 actually populated for this
+/// file are meaningful; the rest of `CameraFileInfoFile` is left as-is by
+/// the driver, so each accessor returns `None` when its bit is unset.
+pub struct FileInfo {
+    inner: ::gphoto2::CameraFileInfo,
+}
+
+impl FileInfo {
+    /// The file's size in bytes.
+    pub fn size(&self) -> Option<u64> {
+        let file = self.inner.file;
+        if file.fields & ::gphoto2::GP_FILE_INFO_SIZE != 0 {
+            Some(file.size as u64)
+        } else {
+            None
+        }
+    }
+
+    /// The file's last-modified time, as a Unix timestamp.
+    pub fn mtime(&self) -> Option<i64> {
+        let file = self.inner.file;
+        if file.fields & ::gphoto2::GP_FILE_INFO_MTIME != 0 {
+            Some(file.mtime as i64)
+        } else {
+            None
+        }
+    }
+
+    /// The file's MIME type, e.g. `image/jpeg`.
+    pub fn mime_type(&self) -> Option<String> {
+        let file = self.inner.file;
+        if file.fields & ::gphoto2::GP_FILE_INFO_TYPE != 0 {
+            Some(unsafe { CStr::from_ptr(file.type_.as_ptr()).to_string_lossy().into_owned() })
+        } else {
+            None
+        }
+    }
+}
+
 mod util {
     use std::ffi::CStr;
+    use libc::c_char;
 
     pub fn camera_text_to_string(mut camera_text: ::gphoto2::CameraText) -> ::Result<String> {
         let length = unsafe {
@@ -246,4 +524,21 @@ mod util {
             ::error::from_libgphoto2(::gphoto2::GP_ERROR_CORRUPTED_DATA)
         })
     }
+
+    /// Copies `src` plus a null terminator into a fixed-size libgphoto2
+    /// char-array field, erroring instead of truncating if it doesn't fit.
+    pub fn write_c_string(dest: &mut [c_char], src: &str) -> ::Result<()> {
+        let bytes = src.as_bytes();
+
+        if bytes.len() >= dest.len() {
+            return Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS));
+        }
+
+        for (slot, &byte) in dest.iter_mut().zip(bytes.iter()) {
+            *slot = byte as c_char;
+        }
+        dest[bytes.len()] = 0;
+
+        Ok(())
+    }
 }
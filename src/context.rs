@@ -0,0 +1,108 @@
+use std::os::raw::{c_char, c_uint, c_void};
+
+/// A libgphoto2 context: carries error reporting, progress, and
+/// cancellation hooks through calls that can take a while, such as
+/// downloads, uploads, and captures.
+pub struct Context {
+    context: *mut ::gphoto2::GPContext,
+    callbacks: Box<Callbacks>,
+}
+
+#[derive(Default)]
+struct Callbacks {
+    progress: Option<Box<FnMut(u32, f32, f32)>>,
+    progress_target: f32,
+    next_progress_id: c_uint,
+    cancel: Option<Box<FnMut() -> bool>>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        let ptr = unsafe { ::gphoto2::gp_context_new() };
+
+        Context { context: ptr, callbacks: Box::new(Callbacks::default()) }
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut ::gphoto2::GPContext {
+        self.context
+    }
+
+    /// Registers a closure to be called with `(id, current, total)` as
+    /// long-running operations make progress.
+    ///
+    /// `id` identifies one progress run (a download, an upload, ...);
+    /// `current`/`total` are in whatever unit the operation reports
+    /// (usually bytes).
+    pub fn on_progress<F>(&mut self, callback: F) where F: FnMut(u32, f32, f32) + 'static {
+        self.callbacks.progress = Some(Box::new(callback));
+
+        unsafe {
+            ::gphoto2::gp_context_set_progress_funcs(
+                self.context,
+                Some(progress_start_trampoline),
+                Some(progress_update_trampoline),
+                Some(progress_stop_trampoline),
+                &mut *self.callbacks as *mut Callbacks as *mut c_void);
+        }
+    }
+
+    /// Registers a predicate polled during long-running operations; once it
+    /// returns `true`, the in-flight `gp_camera_*` call bails out with a
+    /// cancelled error.
+    pub fn on_cancel<F>(&mut self, callback: F) where F: FnMut() -> bool + 'static {
+        self.callbacks.cancel = Some(Box::new(callback));
+
+        unsafe {
+            ::gphoto2::gp_context_set_cancel_func(
+                self.context,
+                Some(cancel_trampoline),
+                &mut *self.callbacks as *mut Callbacks as *mut c_void);
+        }
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        unsafe {
+            ::gphoto2::gp_context_unref(self.context);
+        }
+    }
+}
+
+extern "C" fn progress_start_trampoline(_context: *mut ::gphoto2::GPContext, target: f32, _message: *const c_char, data: *mut c_void) -> c_uint {
+    let callbacks = unsafe { &mut *(data as *mut Callbacks) };
+    callbacks.next_progress_id = callbacks.next_progress_id.wrapping_add(1);
+    let id = callbacks.next_progress_id;
+    callbacks.progress_target = target;
+    if let Some(ref mut callback) = callbacks.progress {
+        callback(id, 0.0, target);
+    }
+    id
+}
+
+extern "C" fn progress_update_trampoline(_context: *mut ::gphoto2::GPContext, id: c_uint, current: f32, data: *mut c_void) {
+    let callbacks = unsafe { &mut *(data as *mut Callbacks) };
+    if let Some(ref mut callback) = callbacks.progress {
+        callback(id, current, callbacks.progress_target);
+    }
+}
+
+extern "C" fn progress_stop_trampoline(_context: *mut ::gphoto2::GPContext, id: c_uint, data: *mut c_void) {
+    let callbacks = unsafe { &mut *(data as *mut Callbacks) };
+    if let Some(ref mut callback) = callbacks.progress {
+        let target = callbacks.progress_target;
+        callback(id, target, target);
+    }
+}
+
+extern "C" fn cancel_trampoline(_context: *mut ::gphoto2::GPContext, data: *mut c_void) -> ::gphoto2::GPContextFeedback {
+    let callbacks = unsafe { &mut *(data as *mut Callbacks) };
+    let cancelled = callbacks.cancel.as_mut().map_or(false, |callback| callback());
+
+    if cancelled {
+        ::gphoto2::GP_CONTEXT_FEEDBACK_CANCEL
+    } else {
+        ::gphoto2::GP_CONTEXT_FEEDBACK_OK
+    }
+}
+
@@ -0,0 +1,52 @@
+use std::ffi::CStr;
+use std::mem;
+use libc::c_int;
+
+/// An iterator over the names in a `gp_camera_folder_list_*` result.
+///
+/// Wraps a `CameraList` and owns it for the lifetime of the iterator,
+/// freeing it when the iterator is dropped.
+pub struct CameraListIter {
+    list: *mut ::gphoto2::CameraList,
+    index: c_int,
+    count: c_int,
+}
+
+pub fn from_libgphoto2(list: *mut ::gphoto2::CameraList) -> ::Result<CameraListIter> {
+    let count = unsafe { ::gphoto2::gp_list_count(list) };
+
+    if count < 0 {
+        unsafe { ::gphoto2::gp_list_free(list) };
+        return Err(::error::from_libgphoto2(count));
+    }
+
+    Ok(CameraListIter { list: list, index: 0, count: count })
+}
+
+impl Iterator for CameraListIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.index >= self.count {
+            return None;
+        }
+
+        let mut ptr = unsafe { mem::uninitialized() };
+        let result = unsafe { ::gphoto2::gp_list_get_name(self.list, self.index, &mut ptr) };
+        self.index += 1;
+
+        if result != ::gphoto2::GP_OK {
+            return None;
+        }
+
+        Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+    }
+}
+
+impl Drop for CameraListIter {
+    fn drop(&mut self) {
+        unsafe {
+            ::gphoto2::gp_list_free(self.list);
+        }
+    }
+}
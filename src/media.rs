@@ -0,0 +1,50 @@
+use std::mem;
+use std::slice;
+use libc::c_ulong;
+
+/// Anything libgphoto2 can read from or write into via a `CameraFile` handle.
+///
+/// Implemented by `MemoryFile` here, and typically also by a thin wrapper
+/// around `std::fs::File` elsewhere in the crate.
+pub trait Media {
+    fn as_mut_ptr(&mut self) -> *mut ::gphoto2::CameraFile;
+}
+
+/// An in-memory `CameraFile`, such as the frame returned by `Camera::capture_preview`.
+pub struct MemoryFile {
+    file: *mut ::gphoto2::CameraFile,
+}
+
+impl MemoryFile {
+    pub fn new() -> ::Result<Self> {
+        let mut ptr = unsafe { mem::uninitialized() };
+
+        try_unsafe!(::gphoto2::gp_file_new(&mut ptr));
+
+        Ok(MemoryFile { file: ptr })
+    }
+
+    /// Returns the file's contents.
+    pub fn as_bytes(&self) -> ::Result<&[u8]> {
+        let mut ptr = unsafe { mem::uninitialized() };
+        let mut len: c_ulong = unsafe { mem::uninitialized() };
+
+        try_unsafe!(::gphoto2::gp_file_get_data_and_size(self.file, &mut ptr, &mut len));
+
+        Ok(unsafe { slice::from_raw_parts(ptr as *const u8, len as usize) })
+    }
+}
+
+impl Media for MemoryFile {
+    fn as_mut_ptr(&mut self) -> *mut ::gphoto2::CameraFile {
+        self.file
+    }
+}
+
+impl Drop for MemoryFile {
+    fn drop(&mut self) {
+        unsafe {
+            ::gphoto2::gp_file_unref(self.file);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::ffi::CStr;
+
+/// Whether a storage medium can be written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessType {
+    ReadWrite,
+    ReadOnly,
+    ReadOnlyWithDelete,
+}
+
+/// How files are laid out on a storage medium.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilesystemType {
+    Generic,
+    Hierarchical,
+    Dcf,
+}
+
+/// The kind of storage medium.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageType {
+    FixedRom,
+    RemovableRom,
+    FixedRam,
+    RemovableRam,
+    Unknown,
+}
+
+/// Information about one of a camera's storage filesystems.
+///
+/// `CameraStorageInformation`'s fields are only meaningful when the
+/// corresponding `GP_STORAGEINFO_*` bit is set in its `fields` mask, so
+/// every accessor here returns `None` rather than guessing at an unset
+/// field's value.
+pub struct Storage {
+    inner: ::gphoto2::CameraStorageInformation,
+}
+
+pub fn from_libgphoto2(inner: ::gphoto2::CameraStorageInformation) -> Storage {
+    Storage { inner: inner }
+}
+
+impl Storage {
+    fn has(&self, flag: ::gphoto2::CameraStorageInfoFields) -> bool {
+        self.inner.fields & flag != 0
+    }
+
+    /// A short, camera-assigned name for this storage, if reported.
+    pub fn label(&self) -> Option<String> {
+        if self.has(::gphoto2::GP_STORAGEINFO_LABEL) {
+            Some(unsafe { CStr::from_ptr(self.inner.label.as_ptr()).to_string_lossy().into_owned() })
+        } else {
+            None
+        }
+    }
+
+    /// A longer description of this storage, if reported.
+    pub fn description(&self) -> Option<String> {
+        if self.has(::gphoto2::GP_STORAGEINFO_DESCRIPTION) {
+            Some(unsafe { CStr::from_ptr(self.inner.description.as_ptr()).to_string_lossy().into_owned() })
+        } else {
+            None
+        }
+    }
+
+    /// Total capacity in bytes, if reported.
+    pub fn capacity_bytes(&self) -> Option<u64> {
+        if self.has(::gphoto2::GP_STORAGEINFO_MAXCAPACITY) {
+            Some(self.inner.capacitykbytes as u64 * 1024)
+        } else {
+            None
+        }
+    }
+
+    /// Free space in bytes, if reported.
+    pub fn free_bytes(&self) -> Option<u64> {
+        if self.has(::gphoto2::GP_STORAGEINFO_FREESPACEKBYTES) {
+            Some(self.inner.freekbytes as u64 * 1024)
+        } else {
+            None
+        }
+    }
+
+    /// Remaining image capacity, if the camera estimates one.
+    pub fn free_images(&self) -> Option<u32> {
+        if self.has(::gphoto2::GP_STORAGEINFO_FREESPACEIMAGES) {
+            Some(self.inner.freeimages as u32)
+        } else {
+            None
+        }
+    }
+
+    /// Whether this storage is writable, if reported.
+    pub fn access_type(&self) -> Option<AccessType> {
+        if !self.has(::gphoto2::GP_STORAGEINFO_ACCESS) {
+            return None;
+        }
+
+        Some(match self.inner.access {
+            ::gphoto2::GP_STORAGEINFO_AT_READ_WRITE => AccessType::ReadWrite,
+            ::gphoto2::GP_STORAGEINFO_AT_READ_ONLY => AccessType::ReadOnly,
+            ::gphoto2::GP_STORAGEINFO_AT_READ_ONLY_WITH_DELETE => AccessType::ReadOnlyWithDelete,
+            _ => return None,
+        })
+    }
+
+    /// How files are laid out on this storage, if reported.
+    pub fn filesystem_type(&self) -> Option<FilesystemType> {
+        if !self.has(::gphoto2::GP_STORAGEINFO_FILESYSTEMTYPE) {
+            return None;
+        }
+
+        Some(match self.inner.fstype {
+            ::gphoto2::GP_STORAGEINFO_FST_GENERICFLAT => FilesystemType::Generic,
+            ::gphoto2::GP_STORAGEINFO_FST_GENERICHIERARCHICAL => FilesystemType::Hierarchical,
+            ::gphoto2::GP_STORAGEINFO_FST_DCF => FilesystemType::Dcf,
+            _ => return None,
+        })
+    }
+
+    /// The kind of storage medium, if reported.
+    pub fn storage_type(&self) -> Option<StorageType> {
+        if !self.has(::gphoto2::GP_STORAGEINFO_STORAGETYPE) {
+            return None;
+        }
+
+        Some(match self.inner.type_ {
+            ::gphoto2::GP_STORAGEINFO_ST_FIXED_ROM => StorageType::FixedRom,
+            ::gphoto2::GP_STORAGEINFO_ST_REMOVABLE_ROM => StorageType::RemovableRom,
+            ::gphoto2::GP_STORAGEINFO_ST_FIXED_RAM => StorageType::FixedRam,
+            ::gphoto2::GP_STORAGEINFO_ST_REMOVABLE_RAM => StorageType::RemovableRam,
+            ::gphoto2::GP_STORAGEINFO_ST_UNKNOWN => StorageType::Unknown,
+            _ => return None,
+        })
+    }
+}
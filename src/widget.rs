@@ -0,0 +1,239 @@
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::mem;
+use std::os::raw::c_void;
+use libc::{c_int, c_float};
+
+/// The kind of setting a `CameraWidget` represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WidgetType {
+    Window,
+    Section,
+    Text,
+    Range,
+    Toggle,
+    Radio,
+    Menu,
+    Button,
+    Date,
+}
+
+fn widget_type_from_raw(raw: ::gphoto2::CameraWidgetType) -> WidgetType {
+    match raw {
+        ::gphoto2::GP_WIDGET_WINDOW => WidgetType::Window,
+        ::gphoto2::GP_WIDGET_SECTION => WidgetType::Section,
+        ::gphoto2::GP_WIDGET_TEXT => WidgetType::Text,
+        ::gphoto2::GP_WIDGET_RANGE => WidgetType::Range,
+        ::gphoto2::GP_WIDGET_TOGGLE => WidgetType::Toggle,
+        ::gphoto2::GP_WIDGET_RADIO => WidgetType::Radio,
+        ::gphoto2::GP_WIDGET_MENU => WidgetType::Menu,
+        ::gphoto2::GP_WIDGET_BUTTON => WidgetType::Button,
+        ::gphoto2::GP_WIDGET_DATE => WidgetType::Date,
+        _ => WidgetType::Text,
+    }
+}
+
+/// The current value of a widget, tagged by the shape libgphoto2 expects for
+/// that widget's type.
+#[derive(Debug, Clone)]
+pub enum WidgetValue {
+    Text(String),
+    Range(f32),
+    Toggle(bool),
+    Radio(String),
+    Menu(String),
+    Date(i32),
+}
+
+/// A single node in the camera's configuration tree.
+///
+/// The tree is rooted at the value returned by `Camera::config`, which owns
+/// the underlying libgphoto2 widget and frees it (recursively, taking every
+/// descendant with it) on drop. Widgets obtained via `get` or `child` are
+/// views into that same tree, so they borrow the widget they were looked up
+/// from: the lifetime `'a` is the root's, which the borrow checker won't let
+/// outlive the root itself.
+pub struct CameraWidget<'a> {
+    widget: *mut ::gphoto2::CameraWidget,
+    owns_tree: bool,
+    _tree: PhantomData<&'a ::gphoto2::CameraWidget>,
+}
+
+impl<'a> Drop for CameraWidget<'a> {
+    fn drop(&mut self) {
+        if self.owns_tree {
+            unsafe {
+                ::gphoto2::gp_widget_free(self.widget);
+            }
+        }
+    }
+}
+
+pub fn from_libgphoto2_root(widget: *mut ::gphoto2::CameraWidget) -> CameraWidget<'static> {
+    CameraWidget { widget: widget, owns_tree: true, _tree: PhantomData }
+}
+
+fn from_libgphoto2_child<'a>(widget: *mut ::gphoto2::CameraWidget) -> CameraWidget<'a> {
+    CameraWidget { widget: widget, owns_tree: false, _tree: PhantomData }
+}
+
+impl<'a> CameraWidget<'a> {
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut ::gphoto2::CameraWidget {
+        self.widget
+    }
+
+    /// Returns the kind of setting this widget represents.
+    pub fn widget_type(&self) -> WidgetType {
+        let mut raw = unsafe { mem::uninitialized() };
+        unsafe {
+            assert_eq!(::gphoto2::GP_OK, ::gphoto2::gp_widget_get_type(self.widget, &mut raw));
+        }
+        widget_type_from_raw(raw)
+    }
+
+    /// Returns the widget's internal name, e.g. `iso`.
+    pub fn name(&self) -> ::Result<String> {
+        let mut ptr = unsafe { mem::uninitialized() };
+        try_unsafe!(::gphoto2::gp_widget_get_name(self.widget, &mut ptr));
+        Ok(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+    }
+
+    /// Returns the widget's human-readable label, e.g. `ISO Speed`.
+    pub fn label(&self) -> ::Result<String> {
+        let mut ptr = unsafe { mem::uninitialized() };
+        try_unsafe!(::gphoto2::gp_widget_get_label(self.widget, &mut ptr));
+        Ok(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+    }
+
+    /// Returns whether the camera currently refuses writes to this setting.
+    pub fn read_only(&self) -> ::Result<bool> {
+        let mut value = unsafe { mem::uninitialized() };
+        try_unsafe!(::gphoto2::gp_widget_get_readonly(self.widget, &mut value));
+        Ok(value != 0)
+    }
+
+    /// Returns the `(min, max, step)` of a `Range` widget.
+    pub fn range(&self) -> ::Result<(f32, f32, f32)> {
+        let mut min: c_float = unsafe { mem::uninitialized() };
+        let mut max: c_float = unsafe { mem::uninitialized() };
+        let mut step: c_float = unsafe { mem::uninitialized() };
+        try_unsafe!(::gphoto2::gp_widget_get_range(self.widget, &mut min, &mut max, &mut step));
+        Ok((min, max, step))
+    }
+
+    /// Returns the number of direct children of this widget.
+    pub fn children_count(&self) -> usize {
+        unsafe { ::gphoto2::gp_widget_count_children(self.widget) as usize }
+    }
+
+    /// Returns the child at `index`.
+    pub fn child(&self, index: usize) -> ::Result<CameraWidget<'a>> {
+        let mut ptr = unsafe { mem::uninitialized() };
+        try_unsafe!(::gphoto2::gp_widget_get_child(self.widget, index as c_int, &mut ptr));
+        Ok(from_libgphoto2_child(ptr))
+    }
+
+    /// Looks up a descendant by full path, name, or label.
+    ///
+    /// Tries `name` as a full path first, then as a label; if neither
+    /// matches and `name` contains a `/`, retries with just the last
+    /// subname, since that's how libgphoto2 addresses settings that live
+    /// under a section (e.g. `main/imgsettings/iso` vs. just `iso`). The
+    /// result carries the same root-tied lifetime as `self`, so chained
+    /// lookups like `config.get("main")?.get("imgsettings")?.get("iso")?`
+    /// work without an intermediate `let` per level.
+    pub fn get(&self, name: &str) -> ::Result<CameraWidget<'a>> {
+        if let Some(widget) = self.child_by_name(name) {
+            return Ok(widget);
+        }
+        if let Some(widget) = self.child_by_label(name) {
+            return Ok(widget);
+        }
+        if let Some(subname) = name.rsplit('/').next() {
+            if subname != name {
+                if let Some(widget) = self.child_by_name(subname) {
+                    return Ok(widget);
+                }
+                if let Some(widget) = self.child_by_label(subname) {
+                    return Ok(widget);
+                }
+            }
+        }
+        Err(::error::from_libgphoto2(::gphoto2::GP_ERROR_BAD_PARAMETERS))
+    }
+
+    fn child_by_name(&self, name: &str) -> Option<CameraWidget<'a>> {
+        let name = CString::new(name).ok()?;
+        let mut ptr = unsafe { mem::uninitialized() };
+        let result = unsafe { ::gphoto2::gp_widget_get_child_by_name(self.widget, name.as_ptr(), &mut ptr) };
+        if result == ::gphoto2::GP_OK {
+            Some(from_libgphoto2_child(ptr))
+        } else {
+            None
+        }
+    }
+
+    fn child_by_label(&self, label: &str) -> Option<CameraWidget<'a>> {
+        let label = CString::new(label).ok()?;
+        let mut ptr = unsafe { mem::uninitialized() };
+        let result = unsafe { ::gphoto2::gp_widget_get_child_by_label(self.widget, label.as_ptr(), &mut ptr) };
+        if result == ::gphoto2::GP_OK {
+            Some(from_libgphoto2_child(ptr))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the widget's current value.
+    pub fn value(&self) -> ::Result<WidgetValue> {
+        match self.widget_type() {
+            WidgetType::Range => {
+                let mut value: c_float = unsafe { mem::uninitialized() };
+                try_unsafe!(::gphoto2::gp_widget_get_value(self.widget, &mut value as *mut _ as *mut c_void));
+                Ok(WidgetValue::Range(value))
+            }
+            WidgetType::Toggle => {
+                let mut value: c_int = unsafe { mem::uninitialized() };
+                try_unsafe!(::gphoto2::gp_widget_get_value(self.widget, &mut value as *mut _ as *mut c_void));
+                Ok(WidgetValue::Toggle(value != 0))
+            }
+            WidgetType::Date => {
+                let mut value: c_int = unsafe { mem::uninitialized() };
+                try_unsafe!(::gphoto2::gp_widget_get_value(self.widget, &mut value as *mut _ as *mut c_void));
+                Ok(WidgetValue::Date(value))
+            }
+            kind => {
+                let mut ptr: *const ::libc::c_char = unsafe { mem::uninitialized() };
+                try_unsafe!(::gphoto2::gp_widget_get_value(self.widget, &mut ptr as *mut _ as *mut c_void));
+                let text = unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() };
+                Ok(match kind {
+                    WidgetType::Radio => WidgetValue::Radio(text),
+                    WidgetType::Menu => WidgetValue::Menu(text),
+                    _ => WidgetValue::Text(text),
+                })
+            }
+        }
+    }
+
+    /// Sets the widget's value locally. Call `Camera::set_config` to push
+    /// the whole tree back to the camera.
+    pub fn set_value(&mut self, value: WidgetValue) -> ::Result<()> {
+        match value {
+            WidgetValue::Range(mut v) => {
+                try_unsafe!(::gphoto2::gp_widget_set_value(self.widget, &mut v as *mut _ as *mut c_void));
+            }
+            WidgetValue::Toggle(v) => {
+                let mut v = v as c_int;
+                try_unsafe!(::gphoto2::gp_widget_set_value(self.widget, &mut v as *mut _ as *mut c_void));
+            }
+            WidgetValue::Date(mut v) => {
+                try_unsafe!(::gphoto2::gp_widget_set_value(self.widget, &mut v as *mut _ as *mut c_void));
+            }
+            WidgetValue::Text(s) | WidgetValue::Radio(s) | WidgetValue::Menu(s) => {
+                let s = CString::new(s).map_err(|_| ::error::from_libgphoto2(::gphoto2::GP_ERROR_CORRUPTED_DATA))?;
+                try_unsafe!(::gphoto2::gp_widget_set_value(self.widget, s.as_ptr() as *mut c_void));
+            }
+        }
+        Ok(())
+    }
+}